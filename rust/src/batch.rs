@@ -0,0 +1,19 @@
+use rayon::prelude::*;
+
+use crate::{Challenge, Solution};
+
+/// Solve many independent challenges across a `rayon` thread pool.
+///
+/// Each individual VDF computation stays sequential (the `t` iterations of a single challenge
+/// cannot be parallelized), but distinct challenges are solved concurrently, one per available
+/// core. Useful for servers validating many users per second, where the current one-challenge-
+/// at-a-time flow leaves most cores idle.
+///
+/// Arguments:
+/// * `challenges` - The challenges to solve.
+///
+/// Returns:
+/// * [`Vec<Solution<'_>>`] - The solutions, in the same order as `challenges`.
+pub fn solve_many(challenges: &[Challenge]) -> Vec<Solution<'_>> {
+    challenges.par_iter().map(Challenge::solve).collect()
+}
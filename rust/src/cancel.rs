@@ -0,0 +1,36 @@
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// A cooperative cancellation handle for [`Challenge::solve_with_progress`].
+///
+/// Cloning a [`CancellationToken`] shares the same underlying flag, so cancelling any clone
+/// cancels every in-progress solve that was handed one. This is checked once per iteration of the
+/// VDF loop, so cancellation takes effect within a single `modpow`, not instantly.
+///
+/// [`Challenge::solve_with_progress`]: crate::Challenge::solve_with_progress
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a new, not-yet-cancelled token.
+    ///
+    /// Returns:
+    /// * [`CancellationToken`] - A fresh token.
+    pub fn new() -> Self {
+        Self(Arc::new(AtomicBool::new(false)))
+    }
+
+    /// Request cancellation of every solve that holds a clone of this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Check whether cancellation has been requested.
+    ///
+    /// Returns:
+    /// * [`bool`] - `true` if [`CancellationToken::cancel()`] has been called on this token or
+    ///   one of its clones.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
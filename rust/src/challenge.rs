@@ -0,0 +1,133 @@
+use alloc::vec::Vec;
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use num_bigint::BigUint;
+
+use crate::{CancellationToken, DuckityError, Solution};
+
+/// The size of a Duckity challenge in bytes.
+pub const CHALLENGE_SIZE: usize = 397;
+
+/// A Duckity challenge.
+///
+/// Use [`Challenge::solve()`] to solve the challenge and get a [`Solution`].
+pub struct Challenge(pub(crate) Vec<u8>);
+
+impl Challenge {
+    /// Decode a Duckity challenge from bytes.
+    ///
+    /// Arguments:
+    /// * `data` - The bytes to decode the challenge from.
+    ///
+    /// Returns:
+    /// * [`Ok<Challenge>`] - The decoded challenge.
+    /// * [`Err<DuckityError>`] - An error if the challenge was invalid.
+    pub fn decode(data: &[u8]) -> Result<Self, DuckityError> {
+        if data.len() != CHALLENGE_SIZE {
+            return Err(DuckityError::DecodingFailed(
+                "The challenge size in bytes was not the expected byte size.",
+            ));
+        }
+
+        Ok(Self(data.to_vec()))
+    }
+
+    /// Get the 'x' value from the challenge.
+    ///
+    /// Returns:
+    /// * [`BigUint`] - The 'x' value.
+    pub fn x(&self) -> BigUint {
+        BigUint::from_bytes_be(&self.0[32..64])
+    }
+
+    /// Get the 'p' value from the challenge.
+    ///
+    /// Returns:
+    /// * [`BigUint`] - The 'p' value.
+    pub fn p(&self) -> BigUint {
+        BigUint::from_bytes_be(&self.0[64..320])
+    }
+
+    /// Get the 't' value from the challenge.
+    ///
+    /// Returns:
+    /// * [`u32`] - The 't' value.
+    pub fn t(&self) -> u32 {
+        u32::from_be_bytes(self.0[320..324].try_into().unwrap())
+    }
+
+    /// Get the client IP address the challenge was issued for.
+    ///
+    /// Returns:
+    /// * [`Ok<IpAddr>`] - The client IP address.
+    /// * [`Err<DuckityError>`] - An error if the IP address could not be decoded.
+    pub fn ip(&self) -> Result<IpAddr, DuckityError> {
+        let client_ip_bytes = &self.0[340..357];
+
+        match client_ip_bytes[0] {
+            4 => {
+                let octets: [u8; 4] = client_ip_bytes[1..5].try_into().expect("The slice had an incorrect length for challenge's IPv4 bytes (expected 4 bytes, but it wasn't 4 bytes)");
+                Ok(IpAddr::V4(Ipv4Addr::from(octets)))
+            }
+            6 => {
+                let octets: [u8; 16] = client_ip_bytes[1..17].try_into().expect("The slice had an incorrect length for challenge's IPv6 bytes (expected 16 bytes, but it wasn't 16 bytes)");
+                Ok(IpAddr::V6(Ipv6Addr::from(octets)))
+            }
+            _ => Err(DuckityError::DecodingFailed(
+                "The challenge contained an invalid IP address version. Only IPv4 and IPv6 are supported.",
+            )),
+        }
+    }
+
+    /// Solve the Duckity challenge.
+    ///
+    /// Note that this operation can be computationally intensive depending on the hardness 't'. Do
+    /// not run this on the main thread in a GUI application, nor in an async function. To run it
+    /// in tokio, for example, use `tokio::task::spawn_blocking`.
+    ///
+    /// Use [`Challenge::solve_with_progress()`] instead if you want progress updates or the
+    /// ability to cancel the computation.
+    ///
+    /// Returns:
+    /// * [`Solution<'_>`] - The solution to the challenge.
+    pub fn solve(&self) -> Solution<'_> {
+        self.solve_with_progress(|_, _| {}, &CancellationToken::new())
+            .expect("solving with a fresh, never-cancelled token always produces a solution")
+    }
+
+    /// Solve the Duckity challenge, reporting progress and allowing cancellation.
+    ///
+    /// `on_progress` is invoked after every completed `modpow` iteration with `(completed,
+    /// total)`, so callers can drive a progress bar. `cancel` is checked once per iteration; if it
+    /// has been cancelled, the computation stops early and `None` is returned.
+    ///
+    /// Arguments:
+    /// * `on_progress` - Called with `(completed, total)` after each iteration.
+    /// * `cancel` - A token that aborts the computation when cancelled.
+    ///
+    /// Returns:
+    /// * [`Some<Solution<'_>>`] - The solution, if the computation ran to completion.
+    /// * [`None`] - If `cancel` was cancelled before the computation finished.
+    pub fn solve_with_progress(
+        &self,
+        mut on_progress: impl FnMut(u32, u32),
+        cancel: &CancellationToken,
+    ) -> Option<Solution<'_>> {
+        let x = self.x();
+        let p = self.p();
+        let t = self.t();
+        let e = (&p + (BigUint::ZERO + 1u8)) >> 2; // (p+1)/4, hoisted out of the loop below since it's invariant across iterations
+
+        let mut y = x;
+        for completed in 0..t {
+            if cancel.is_cancelled() {
+                return None;
+            }
+
+            y = y.modpow(&e, &p);
+            on_progress(completed + 1, t);
+        }
+
+        Some(Solution(self, y))
+    }
+}
@@ -0,0 +1,453 @@
+use std::{net::IpAddr, time::Duration};
+
+use rayon::prelude::*;
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use serde::Serialize;
+
+use crate::{Challenge, DuckityError};
+
+/// A client for interacting with the Duckity API.
+///
+/// To create a new client, use [`DuckityClient::new()`]. If you're using a self-hosted duckling,
+/// use [`DuckityClient::with_domain()`] to point to your custom domain. For control over the TLS
+/// backend, timeout, and retry behavior, use [`DuckityClient::builder()`] instead.
+///
+/// To get a challenge, use [`DuckityClient::get_challenge()`]. To solve it, use
+/// [`Challenge::solve()`]. Use [`Solution::encode()`] to get the encoded solution string.
+///
+/// [`Solution::encode()`]: crate::Solution::encode
+#[derive(Debug, Clone)]
+pub struct DuckityClient {
+    /// The domain the client is pointing to.
+    domain: String,
+    /// The HMAC key used to verify challenges fetched via [`DuckityClient::get_challenge()`], if
+    /// any. See [`Challenge::verify()`].
+    verification_key: Option<Vec<u8>>,
+    /// The underlying HTTP client, reused across requests.
+    http: Client,
+    /// The retry policy applied to idempotent requests on transient failures.
+    retry_policy: RetryPolicy,
+}
+
+impl DuckityClient {
+    /// Create a new Duckity client with the default TLS backend, a 10 second timeout, and the
+    /// default [`RetryPolicy`].
+    ///
+    /// Use [`DuckityClient::with_domain()`] instead if you want to point to a custom domain, or
+    /// [`DuckityClient::builder()`] for full control over the transport.
+    ///
+    /// Returns:
+    /// [`DuckityClient`] - A new Duckity client.
+    pub fn new() -> Self {
+        Self::builder()
+            .build()
+            .expect("the default client configuration is always valid")
+    }
+
+    /// Create a new Duckity client pointing to a custom domain.
+    ///
+    /// Use this if you're self-hosting Duckity or using a different environment.
+    ///
+    /// Arguments:
+    /// * `domain` - The domain to point the client to.
+    ///
+    /// Returns:
+    /// [`DuckityClient`] - A new Duckity client.
+    pub fn with_domain(domain: impl ToString) -> Self {
+        Self::builder()
+            .domain(domain)
+            .build()
+            .expect("the default client configuration is always valid")
+    }
+
+    /// Create a [`DuckityClientBuilder`] for configuring the TLS backend, timeout, and retry
+    /// policy before building a [`DuckityClient`].
+    ///
+    /// Returns:
+    /// [`DuckityClientBuilder`] - A new builder, seeded with the same defaults as
+    /// [`DuckityClient::new()`].
+    pub fn builder() -> DuckityClientBuilder {
+        DuckityClientBuilder::new()
+    }
+
+    /// Set the HMAC key used to verify the authenticity of challenges fetched via
+    /// [`DuckityClient::get_challenge()`], which rejects any challenge that fails verification
+    /// with [`DuckityError::UntrustedChallenge`] before it can be solved.
+    ///
+    /// Arguments:
+    /// * `key` - The HMAC key shared with the Duckity server operator out of band.
+    ///
+    /// Returns:
+    /// [`DuckityClient`] - The client with the verification key set.
+    pub fn with_verification_key(mut self, key: impl Into<Vec<u8>>) -> Self {
+        self.verification_key = Some(key.into());
+        self
+    }
+
+    /// Get a challenge for the given application ID and profile code.
+    ///
+    /// Transient failures (connection errors, timeouts, 429s, and 5xxs) are retried according to
+    /// the client's [`RetryPolicy`].
+    ///
+    /// Arguments:
+    /// * `app_id` - The application ID to get the challenge for.
+    /// * `profile_code` - The profile code to use for the challenge.
+    ///
+    /// Returns:
+    /// * [`Ok<Challenge>`] - The challenge if successful.
+    /// * [`Err<DuckityError>`] - An error if the request failed.
+    pub async fn get_challenge(
+        &self,
+        app_id: impl ToString,
+        profile_code: impl ToString,
+    ) -> Result<Challenge, DuckityError> {
+        let payload = ChallengeRequestPayload {
+            profile: profile_code.to_string(),
+        };
+        let app_id = app_id.to_string();
+
+        let response = self
+            .send_with_retries(|| {
+                self.http
+                    .post(format!("https://{}/v1/challenges/{}", self.domain, app_id))
+                    .json(&payload)
+            })
+            .await?;
+
+        if response.status().is_success() {
+            let bytes = response.bytes().await?;
+
+            let challenge = Challenge::decode(&bytes)?;
+
+            if let Some(key) = &self.verification_key {
+                challenge.verify(key)?;
+            }
+
+            Ok(challenge)
+        } else {
+            let error_response: ErrorResponse = response.json().await?;
+
+            Err(DuckityError::ApiError(
+                error_response.title,
+                error_response.message,
+            ))
+        }
+    }
+
+    /// Validate a challenge solution with the server.
+    ///
+    /// Transient failures (connection errors, timeouts, 429s, and 5xxs) are retried according to
+    /// the client's [`RetryPolicy`].
+    ///
+    /// Arguments:
+    /// * `app_id` - The application ID the challenge was issued for.
+    /// * `app_secret` - The application secret for authentication.
+    /// * `profile_code` - The profile code used for the challenge.
+    /// * `solution` - The solution to validate, as a base64 URL-safe encoded string.
+    /// * `client_ip` - The client IP address the challenge was issued for.
+    ///
+    /// Returns:
+    /// * [`Ok<()>`] - If the validation was successful.
+    /// * [`Err<DuckityError>`] - An error if the validation failed.
+    pub async fn validate_challenge(
+        &self,
+        app_id: impl ToString,
+        app_secret: impl ToString,
+        profile_code: impl ToString,
+        solution: String,
+        client_ip: IpAddr,
+    ) -> Result<(), DuckityError> {
+        let payload = ValidationRequest {
+            token: solution,
+            ip: client_ip,
+            profile: profile_code.to_string(),
+        };
+        let app_id = app_id.to_string();
+        let app_secret = app_secret.to_string();
+
+        let response = self
+            .send_with_retries(|| {
+                self.http
+                    .post(format!(
+                        "https://{}/v1/challenges/{}/validate",
+                        self.domain, app_id
+                    ))
+                    .json(&payload)
+                    .bearer_auth(&app_secret)
+            })
+            .await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            let error_response: ErrorResponse = response.json().await?;
+
+            Err(DuckityError::ApiError(
+                error_response.title,
+                error_response.message,
+            ))
+        }
+    }
+
+    /// Fetch challenges for a slice of profile codes concurrently, then solve each one across a
+    /// `rayon` thread pool.
+    ///
+    /// This amortizes the cost of validating many users per second: the challenges are fetched
+    /// concurrently instead of one request at a time, and each is solved on its own core instead
+    /// of leaving the rest idle.
+    ///
+    /// Arguments:
+    /// * `app_id` - The application ID to get challenges for.
+    /// * `profile_codes` - The profile codes to fetch and solve a challenge for.
+    ///
+    /// Returns:
+    /// * [`Vec<Result<(Challenge, String), DuckityError>>`] - One result per profile code, in the
+    ///   same order as `profile_codes`, pairing the fetched [`Challenge`] with its encoded
+    ///   [`Solution`].
+    ///
+    /// [`Solution`]: crate::Solution
+    pub async fn get_and_solve_batch(
+        &self,
+        app_id: impl ToString,
+        profile_codes: &[impl ToString],
+    ) -> Vec<Result<(Challenge, String), DuckityError>> {
+        let app_id = app_id.to_string();
+
+        let challenges = futures::future::join_all(
+            profile_codes
+                .iter()
+                .map(|profile_code| self.get_challenge(app_id.clone(), profile_code.to_string())),
+        )
+        .await;
+
+        // Solving blocks the calling thread for the full VDF duration (see the note on
+        // `Challenge::solve()`), so the rayon fan-out itself must run on a blocking thread
+        // rather than a tokio worker.
+        tokio::task::spawn_blocking(move || {
+            challenges
+                .into_par_iter()
+                .map(|result| {
+                    result.map(|challenge| {
+                        let encoded = challenge.solve().encode();
+                        (challenge, encoded)
+                    })
+                })
+                .collect()
+        })
+        .await
+        .expect("the blocking solve task does not panic")
+    }
+
+    /// Send a request, retrying transient failures with exponential backoff according to
+    /// [`Self::retry_policy`].
+    ///
+    /// `build_request` is called fresh for every attempt, since a [`RequestBuilder`] is consumed
+    /// by [`RequestBuilder::send()`].
+    async fn send_with_retries(
+        &self,
+        build_request: impl Fn() -> RequestBuilder,
+    ) -> Result<Response, DuckityError> {
+        let mut attempt = 0;
+
+        loop {
+            match build_request().send().await {
+                Ok(response) => {
+                    let retries_exhausted = attempt >= self.retry_policy.max_retries;
+
+                    if retries_exhausted || !self.retry_policy.should_retry_status(response.status()) {
+                        return Ok(response);
+                    }
+                }
+                Err(error) => {
+                    let retries_exhausted = attempt >= self.retry_policy.max_retries;
+                    let error = DuckityError::RequestFailed(error);
+
+                    if retries_exhausted || !error.is_retryable() {
+                        return Err(error);
+                    }
+                }
+            }
+
+            tokio::time::sleep(self.retry_policy.backoff(attempt)).await;
+            attempt += 1;
+        }
+    }
+}
+
+impl Default for DuckityClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The TLS backend used by a [`DuckityClient`]'s underlying HTTP client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TlsBackend {
+    /// Use the platform's native TLS implementation (OpenSSL, Schannel, or Secure Transport).
+    #[default]
+    NativeTls,
+    /// Use `rustls`, a pure-Rust TLS implementation with no native dependency.
+    Rustls,
+}
+
+/// A bounded exponential backoff policy for transient request failures (connection errors,
+/// timeouts, 429s, and 5xxs).
+///
+/// The delay before retry `n`, counting from zero, is `base_delay * 2^n`, so the first retry
+/// waits `base_delay`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    base_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Create a new retry policy.
+    ///
+    /// Arguments:
+    /// * `max_retries` - The maximum number of retries after the initial attempt.
+    /// * `base_delay` - The delay before the first retry; doubled for each subsequent retry.
+    ///
+    /// Returns:
+    /// [`RetryPolicy`] - A new retry policy.
+    pub fn new(max_retries: u32, base_delay: Duration) -> Self {
+        Self {
+            max_retries,
+            base_delay,
+        }
+    }
+
+    /// A policy that never retries.
+    ///
+    /// Returns:
+    /// [`RetryPolicy`] - A retry policy with zero retries.
+    pub fn none() -> Self {
+        Self::new(0, Duration::ZERO)
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.base_delay * 2u32.saturating_pow(attempt)
+    }
+
+    fn should_retry_status(&self, status: StatusCode) -> bool {
+        status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(3, Duration::from_millis(200))
+    }
+}
+
+/// A builder for [`DuckityClient`], letting callers reuse a single [`reqwest::Client`], choose a
+/// TLS backend, set the request timeout, and configure retry behavior for transient failures.
+///
+/// Create one with [`DuckityClient::builder()`].
+pub struct DuckityClientBuilder {
+    domain: String,
+    verification_key: Option<Vec<u8>>,
+    tls_backend: TlsBackend,
+    timeout: Duration,
+    retry_policy: RetryPolicy,
+}
+
+impl DuckityClientBuilder {
+    fn new() -> Self {
+        Self {
+            domain: "quack.duckity.dev".to_string(),
+            verification_key: None,
+            tls_backend: TlsBackend::default(),
+            timeout: Duration::from_secs(10),
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Point the client at a custom domain, e.g. for a self-hosted duckling.
+    ///
+    /// Arguments:
+    /// * `domain` - The domain to point the client to.
+    pub fn domain(mut self, domain: impl ToString) -> Self {
+        self.domain = domain.to_string();
+        self
+    }
+
+    /// Set the HMAC key used to verify the authenticity of challenges fetched via
+    /// [`DuckityClient::get_challenge()`].
+    ///
+    /// Arguments:
+    /// * `key` - The HMAC key shared with the Duckity server operator out of band.
+    pub fn verification_key(mut self, key: impl Into<Vec<u8>>) -> Self {
+        self.verification_key = Some(key.into());
+        self
+    }
+
+    /// Choose the TLS backend used for outgoing requests. Defaults to
+    /// [`TlsBackend::NativeTls`].
+    ///
+    /// Arguments:
+    /// * `tls_backend` - The TLS backend to use.
+    pub fn tls_backend(mut self, tls_backend: TlsBackend) -> Self {
+        self.tls_backend = tls_backend;
+        self
+    }
+
+    /// Set the per-request timeout. Defaults to 10 seconds.
+    ///
+    /// Arguments:
+    /// * `timeout` - The per-request timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the retry policy used for idempotent requests on transient failures. Defaults to
+    /// [`RetryPolicy::default()`].
+    ///
+    /// Arguments:
+    /// * `retry_policy` - The retry policy to use.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Build the [`DuckityClient`].
+    ///
+    /// Returns:
+    /// * [`Ok<DuckityClient>`] - The configured client.
+    /// * [`Err<DuckityError>`] - If the underlying HTTP client couldn't be constructed.
+    pub fn build(self) -> Result<DuckityClient, DuckityError> {
+        let mut builder = Client::builder().timeout(self.timeout);
+
+        builder = match self.tls_backend {
+            TlsBackend::NativeTls => builder.use_native_tls(),
+            TlsBackend::Rustls => builder.use_rustls_tls(),
+        };
+
+        Ok(DuckityClient {
+            domain: self.domain,
+            verification_key: self.verification_key,
+            http: builder.build()?,
+            retry_policy: self.retry_policy,
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct ChallengeRequestPayload {
+    /// The profile code to use for the challenge.
+    profile: String,
+}
+
+#[derive(serde::Serialize)]
+struct ValidationRequest {
+    token: String,
+    ip: IpAddr,
+    profile: String,
+}
+
+#[derive(serde::Deserialize)]
+struct ErrorResponse {
+    title: String,
+    message: String,
+}
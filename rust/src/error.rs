@@ -0,0 +1,42 @@
+use alloc::string::String;
+
+/// An error that can occur when using the Duckity client.
+#[derive(Debug, thiserror::Error)]
+pub enum DuckityError {
+    /// An error occurred with the Duckity client while making an HTTP request.
+    #[cfg(feature = "client")]
+    #[error("An error occurred with the Duckity client while making an HTTP request: {0}")]
+    RequestFailed(#[from] reqwest::Error),
+
+    /// An error occurred while decoding the challenge.
+    #[error(
+        "An error occurred while decoding the challenge. Did the API return a valid response? {0}"
+    )]
+    DecodingFailed(&'static str),
+
+    /// The challenge failed authenticity verification and was rejected before solving.
+    #[error("The challenge failed authenticity verification and was rejected before solving.")]
+    UntrustedChallenge,
+
+    /// An API error occurred.
+    #[error("An API error occurred: {0}: {1}")]
+    ApiError(String, String),
+}
+
+impl DuckityError {
+    /// Whether this error is transient and worth retrying (a connection error or a timeout), as
+    /// opposed to fatal (a decoding failure, a rejected challenge, or an API error). `429` and
+    /// `5xx` responses are retried from their status code before they become a
+    /// [`DuckityError::ApiError`], which this reports as not retryable.
+    ///
+    /// Returns:
+    /// * `true` - If the request that produced this error is worth retrying.
+    /// * `false` - If the error is fatal and retrying would not help.
+    #[cfg(feature = "client")]
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::RequestFailed(error) => error.is_connect() || error.is_timeout(),
+            Self::DecodingFailed(_) | Self::UntrustedChallenge | Self::ApiError(_, _) => false,
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use base64::{Engine, prelude::BASE64_URL_SAFE_NO_PAD};
+use num_bigint::BigUint;
+
+use crate::{CHALLENGE_SIZE, Challenge};
+
+/// The solution to a Duckity challenge.
+pub struct Solution<'a>(pub(crate) &'a Challenge, pub(crate) BigUint);
+
+impl Solution<'_> {
+    /// Encode the solution as a base64 URL-safe string.
+    ///
+    /// Returns:
+    /// * [`String`] - The encoded solution.
+    pub fn encode(&self) -> String {
+        let mut buf = Vec::with_capacity(CHALLENGE_SIZE + 256);
+
+        buf.extend_from_slice(&self.0.0);
+        buf.extend_from_slice(&self.1.to_bytes_be());
+
+        BASE64_URL_SAFE_NO_PAD.encode(buf)
+    }
+
+    /// Get the raw size of the solution in bytes.
+    ///
+    /// Returns:
+    /// * [`usize`] - The size of the solution in bytes.
+    pub fn raw_size(&self) -> usize {
+        self.0.0.len() + self.1.to_bytes_be().len()
+    }
+}
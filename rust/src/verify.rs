@@ -0,0 +1,34 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::{Challenge, DuckityError};
+
+type HmacSha256 = Hmac<Sha256>;
+
+impl Challenge {
+    /// Verify the challenge's authenticity before spending CPU on [`Challenge::solve()`].
+    ///
+    /// A malicious or buggy server can hand out a bogus challenge and make the client burn large
+    /// amounts of sequential `modpow` work on garbage. The leading 32 bytes of the challenge
+    /// (reserved, otherwise unused) are treated as an HMAC-SHA256 tag over the literal `x‖p‖t‖ip`
+    /// region, keyed with `key` — that's `x` (`32..64`), `p` (`64..320`), and `t` (`320..324`)
+    /// concatenated with `ip` (`340..357`), skipping the reserved gap at `324..340` which isn't
+    /// part of the tagged data. Reject the challenge if this fails before calling
+    /// [`Challenge::solve()`].
+    ///
+    /// Arguments:
+    /// * `key` - The HMAC key shared with the Duckity server operator out of band.
+    ///
+    /// Returns:
+    /// * [`Ok(())`] - If the challenge's tag matches.
+    /// * [`Err<DuckityError>`] - [`DuckityError::UntrustedChallenge`] if the tag doesn't match.
+    pub fn verify(&self, key: &[u8]) -> Result<(), DuckityError> {
+        let mut mac =
+            HmacSha256::new_from_slice(key).expect("HMAC-SHA256 accepts a key of any length");
+        mac.update(&self.0[32..324]); // x‖p‖t
+        mac.update(&self.0[340..357]); // ip
+
+        mac.verify_slice(&self.0[0..32])
+            .map_err(|_| DuckityError::UntrustedChallenge)
+    }
+}
@@ -1,10 +1,20 @@
 #![doc = include_str!("../README.md")]
 
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
 use base64::{Engine, prelude::BASE64_URL_SAFE_NO_PAD};
-use gloo_net::http::Request;
+use gloo_net::http::{Request, Response};
+use hmac::{Hmac, Mac};
+use js_sys::Function;
 use num_bigint::BigUint;
 use serde::Serialize;
+use sha2::Sha256;
 use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::JsValue;
+
+type HmacSha256 = Hmac<Sha256>;
 
 /// The size of a Duckity challenge in bytes.
 const CHALLENGE_SIZE: usize = 397;
@@ -20,6 +30,11 @@ const CHALLENGE_SIZE: usize = 397;
 pub struct DuckityClient {
     /// The domain the client is pointing to.
     domain: String,
+    /// The HMAC key used to verify challenges fetched via `DuckityClient.get_challenge()`, if
+    /// any. See `Challenge.verify()`.
+    verification_key: Option<Vec<u8>>,
+    /// The retry policy applied to `get_challenge()` on transient failures.
+    retry_policy: RetryPolicy,
 }
 
 #[wasm_bindgen]
@@ -34,6 +49,8 @@ impl DuckityClient {
     pub fn new() -> Self {
         Self {
             domain: "quack.duckity.dev".to_string(),
+            verification_key: None,
+            retry_policy: RetryPolicy::default(),
         }
     }
 
@@ -48,11 +65,42 @@ impl DuckityClient {
     /// `DuckityClient` - A new Duckity client.
     #[wasm_bindgen]
     pub fn with_domain(domain: String) -> Self {
-        Self { domain }
+        Self {
+            domain,
+            verification_key: None,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Set the HMAC key used to verify the authenticity of challenges fetched via
+    /// `DuckityClient.get_challenge()`, which rejects any challenge that fails verification
+    /// before it can be solved.
+    ///
+    /// Arguments:
+    /// * `key` - The HMAC key shared with the Duckity server operator out of band.
+    #[wasm_bindgen(js_name = setVerificationKey)]
+    pub fn set_verification_key(&mut self, key: Vec<u8>) {
+        self.verification_key = Some(key);
+    }
+
+    /// Set the retry policy applied to `get_challenge()` on transient failures (network errors,
+    /// `429`s, and `5xx`s). There's no TLS backend to choose in a browser, but retrying transient
+    /// failures with backoff is still worthwhile.
+    ///
+    /// Arguments:
+    /// * `max_retries` - The maximum number of retries after the initial attempt.
+    /// * `base_delay_ms` - The delay, in milliseconds, before the first retry; doubled for each
+    ///   subsequent retry.
+    #[wasm_bindgen(js_name = setRetryPolicy)]
+    pub fn set_retry_policy(&mut self, max_retries: u32, base_delay_ms: u32) {
+        self.retry_policy = RetryPolicy::new(max_retries, Duration::from_millis(base_delay_ms.into()));
     }
 
     /// Get a challenge for the given application ID and profile code.
     ///
+    /// Transient failures (network errors, 429s, and 5xxs) are retried according to the client's
+    /// retry policy (see `DuckityClient.setRetryPolicy()`).
+    ///
     /// Arguments:
     /// * `app_id` - The application ID to get the challenge for.
     /// * `profile_code` - The profile code to use for the challenge.
@@ -69,19 +117,19 @@ impl DuckityClient {
         let payload = ChallengeRequestPayload {
             profile: profile_code.to_string(),
         };
+        let url = format!("https://{}/v1/challenges/{}", self.domain, app_id);
 
-        let response = Request::post(&format!("https://{}/v1/challenges/{}", self.domain, app_id))
-            .json(&payload)
-            .unwrap()
-            .send()
-            .await
-            .err_to_string()?;
+        let response = self.send_with_retries(&url, &payload).await.err_to_string()?;
 
         if response.status() == 200 {
             let bytes = response.binary().await.err_to_string()?;
 
             let challenge = Challenge::decode(&bytes).err_to_string()?;
 
+            if let Some(key) = &self.verification_key {
+                challenge.verify(key.clone())?;
+            }
+
             Ok(challenge)
         } else {
             let error_response: ErrorResponse = response.json().await.err_to_string()?;
@@ -93,6 +141,39 @@ impl DuckityClient {
             .err_to_string()
         }
     }
+
+    /// Send the challenge request, retrying transient failures with exponential backoff
+    /// according to `self.retry_policy`.
+    async fn send_with_retries(
+        &self,
+        url: &str,
+        payload: &ChallengeRequestPayload,
+    ) -> Result<Response, DuckityError> {
+        let mut attempt = 0;
+
+        loop {
+            let result = Request::post(url)
+                .json(payload)
+                .expect("a ChallengeRequestPayload always serializes to valid JSON")
+                .send()
+                .await
+                .map_err(DuckityError::from);
+
+            let retries_exhausted = attempt >= self.retry_policy.max_retries;
+
+            match result {
+                Ok(response) if retries_exhausted || !self.retry_policy.should_retry_status(response.status()) => {
+                    return Ok(response);
+                }
+                Ok(_) => {}
+                Err(error) if retries_exhausted || !error.is_retryable() => return Err(error),
+                Err(_) => {}
+            }
+
+            gloo_timers::future::sleep(self.retry_policy.backoff(attempt)).await;
+            attempt += 1;
+        }
+    }
 }
 
 impl Default for DuckityClient {
@@ -101,6 +182,40 @@ impl Default for DuckityClient {
     }
 }
 
+/// A bounded exponential backoff policy for transient `get_challenge()` failures (network
+/// errors, 429s, and 5xxs).
+///
+/// The delay before retry `n`, counting from zero, is `base_delay * 2^n`, so the first retry
+/// waits `base_delay`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RetryPolicy {
+    max_retries: u32,
+    base_delay: Duration,
+}
+
+impl RetryPolicy {
+    fn new(max_retries: u32, base_delay: Duration) -> Self {
+        Self {
+            max_retries,
+            base_delay,
+        }
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.base_delay * 2u32.saturating_pow(attempt)
+    }
+
+    fn should_retry_status(&self, status: u16) -> bool {
+        status == 429 || (500..600).contains(&status)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(3, Duration::from_millis(200))
+    }
+}
+
 trait DuckityResultToString<T> {
     fn err_to_string(self) -> Result<T, String>;
 }
@@ -131,11 +246,29 @@ pub enum DuckityError {
     )]
     DecodingFailed(&'static str),
 
+    /// The challenge failed authenticity verification and was rejected before solving.
+    #[error("The challenge failed authenticity verification and was rejected before solving.")]
+    UntrustedChallenge,
+
     /// An API error occurred.
     #[error("An API error occurred: {0}: {1}")]
     ApiError(String, String),
 }
 
+impl DuckityError {
+    /// Whether this error is transient and worth retrying (a network error), as opposed to fatal
+    /// (a decoding failure, a rejected challenge, or an API error). `429` and `5xx` responses are
+    /// retried from their status code before they become a `DuckityError::ApiError`, which this
+    /// reports as not retryable.
+    ///
+    /// Returns:
+    /// * `true` - If the request that produced this error is worth retrying.
+    /// * `false` - If the error is fatal and retrying would not help.
+    fn is_retryable(&self) -> bool {
+        matches!(self, Self::RequestFailed(_))
+    }
+}
+
 #[derive(Serialize)]
 struct ChallengeRequestPayload {
     /// The profile code to use for the challenge.
@@ -193,6 +326,32 @@ impl Challenge {
         u32::from_be_bytes(self.0[320..324].try_into().unwrap())
     }
 
+    /// Verify the challenge's authenticity before spending CPU on `Challenge.solve()`.
+    ///
+    /// The leading 32 bytes of the challenge (reserved, otherwise unused) are treated as an
+    /// HMAC-SHA256 tag over the literal `x‖p‖t‖ip` region, keyed with `key` — that's `x`
+    /// (`32..64`), `p` (`64..320`), and `t` (`320..324`) concatenated with `ip` (`340..357`),
+    /// skipping the reserved gap at `324..340` which isn't part of the tagged data. Reject the
+    /// challenge if this fails before calling `Challenge.solve()`.
+    ///
+    /// Arguments:
+    /// * `key` - The HMAC key shared with the Duckity server operator out of band.
+    ///
+    /// Returns:
+    /// * `undefined` - If the challenge's tag matches.
+    /// * `String` - An error string if the tag doesn't match.
+    #[wasm_bindgen]
+    pub fn verify(&self, key: Vec<u8>) -> Result<(), String> {
+        let mut mac = HmacSha256::new_from_slice(&key)
+            .expect("HMAC-SHA256 accepts a key of any length");
+        mac.update(&self.0[32..324]); // x‖p‖t
+        mac.update(&self.0[340..357]); // ip
+
+        mac.verify_slice(&self.0[0..32])
+            .map_err(|_| DuckityError::UntrustedChallenge)
+            .err_to_string()
+    }
+
     /// Solve the Duckity challenge.
     ///
     /// Note that this operation is computationally-intensive. Make sure to run it from a worker
@@ -202,17 +361,82 @@ impl Challenge {
     /// * `Solution` - The solution to the challenge.
     #[wasm_bindgen]
     pub fn solve(&self) -> Solution {
+        self.solve_with_progress(None, None)
+            .expect("solving without a cancellation token always produces a solution")
+    }
+
+    /// Solve the Duckity challenge, reporting progress and allowing cancellation.
+    ///
+    /// `on_progress`, if given, is called after every completed iteration with `(completed,
+    /// total)` so callers can drive a progress bar. `cancel`, if given, is checked once per
+    /// iteration; once cancelled (e.g. from an `AbortSignal`'s `abort` listener calling
+    /// `CancellationToken.cancel()`), the computation stops early and `undefined` is returned.
+    ///
+    /// Arguments:
+    /// * `on_progress` - A `(completed: number, total: number) => void` callback, or `null`.
+    /// * `cancel` - A `CancellationToken` to observe, or `null`.
+    ///
+    /// Returns:
+    /// * `Solution` - The solution, if the computation ran to completion.
+    /// * `undefined` - If `cancel` was cancelled before the computation finished.
+    #[wasm_bindgen(js_name = solveWithProgress)]
+    pub fn solve_with_progress(
+        &self,
+        on_progress: Option<Function>,
+        cancel: Option<CancellationToken>,
+    ) -> Option<Solution> {
         let x = self.x();
         let p = self.p();
         let t = self.t();
+        let e = (&p + (BigUint::ZERO + 1u8)) >> 2; // (p+1)/4
 
         let mut y = x;
-        for _ in 0..t {
-            let e = (&p + (BigUint::ZERO + 1u8)) >> 2; // (p+1)/4
+        for completed in 0..t {
+            if cancel.as_ref().is_some_and(CancellationToken::is_cancelled) {
+                return None;
+            }
+
             y = y.modpow(&e, &p);
+
+            if let Some(on_progress) = &on_progress {
+                let _ = on_progress.call2(
+                    &JsValue::NULL,
+                    &JsValue::from(completed + 1),
+                    &JsValue::from(t),
+                );
+            }
         }
 
-        Solution(self.clone(), y)
+        Some(Solution(self.clone(), y))
+    }
+}
+
+/// A cooperative cancellation handle for [`Challenge::solveWithProgress`], compatible with the
+/// browser `AbortSignal` pattern: call `cancel()` from an `abort` event listener to stop an
+/// in-progress solve.
+///
+/// [`Challenge::solveWithProgress`]: Challenge::solve_with_progress
+#[wasm_bindgen]
+#[derive(Clone, Default)]
+pub struct CancellationToken(Rc<Cell<bool>>);
+
+#[wasm_bindgen]
+impl CancellationToken {
+    /// Create a new, not-yet-cancelled token.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation of every solve that holds a clone of this token.
+    pub fn cancel(&self) {
+        self.0.set(true);
+    }
+
+    /// Check whether cancellation has been requested.
+    #[wasm_bindgen(js_name = isCancelled)]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.get()
     }
 }
 